@@ -0,0 +1,160 @@
+//! A small client for an on-disk `pass` store: enumerating the entries it
+//! holds and decrypting them by shelling out to the `pass` binary, so
+//! callers don't have to reimplement directory traversal and subprocess
+//! plumbing themselves.
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::Entry;
+
+/// A `pass` password store rooted at a directory on disk.
+#[derive(Debug, Clone)]
+pub struct Store {
+    path: PathBuf,
+}
+
+/// An error from loading or decrypting a store entry.
+#[derive(Debug)]
+pub enum StoreError {
+    /// The `pass` binary couldn't be spawned.
+    Io(std::io::Error),
+    /// `pass` ran but exited non-zero; carries its stderr.
+    CommandFailed(String),
+    /// `pass` succeeded but its output didn't decode as an `Entry`.
+    Decode(&'static str),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "failed to run pass: {}", e),
+            StoreError::CommandFailed(stderr) => write!(f, "pass failed: {}", stderr),
+            StoreError::Decode(e) => write!(f, "failed to decode entry: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StoreError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for StoreError {
+    fn from(e: std::io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+impl Store {
+    /// Open a store rooted at `path`, or at `$PASSWORD_STORE_DIR` (falling
+    /// back to `~/.password-store`) if `path` is `None`.
+    pub fn open(path: Option<PathBuf>) -> Store {
+        Store {
+            path: path.unwrap_or_else(default_store_dir),
+        }
+    }
+
+    /// The root directory this store reads from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Recursively list the logical entry names (`*.gpg` files under the
+    /// store root, with the extension stripped and path separators
+    /// normalized to `/`) in sorted order.
+    pub fn list(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        collect_gpg_names(&self.path, &self.path, &mut names);
+        names.sort();
+        names
+    }
+
+    /// Decrypt and parse the entry named `name` by invoking `pass <name>`.
+    pub fn get(&self, name: &str) -> Result<Entry, StoreError> {
+        let output = Command::new("pass")
+            .arg(name)
+            .env("PASSWORD_STORE_DIR", &self.path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(StoreError::CommandFailed(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Entry::from_utf8(name, &output.stdout).map_err(StoreError::Decode)
+    }
+}
+
+fn default_store_dir() -> PathBuf {
+    if let Ok(dir) = env::var("PASSWORD_STORE_DIR") {
+        return PathBuf::from(dir);
+    }
+    env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".password-store"))
+        .unwrap_or_else(|_| PathBuf::from(".password-store"))
+}
+
+/// Walk `dir` (relative to `root`) collecting `*.gpg` file names, skipping
+/// `.git` (pass stores are commonly version-controlled).
+fn collect_gpg_names(root: &Path, dir: &Path, names: &mut Vec<String>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path.file_name().and_then(|n| n.to_str()) == Some(".git") {
+                continue;
+            }
+            collect_gpg_names(root, &path, names);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("gpg") {
+            if let Ok(relative) = path.strip_prefix(root) {
+                if let Some(name) = relative.with_extension("").to_str() {
+                    names.push(name.replace(std::path::MAIN_SEPARATOR, "/"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_finds_nested_gpg_files() {
+        let dir = env::temp_dir().join(format!("pass-rust-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("email")).unwrap();
+        fs::write(dir.join("myEmail.gpg"), b"").unwrap();
+        fs::write(dir.join("email/work.gpg"), b"").unwrap();
+        fs::write(dir.join("ignored.txt"), b"").unwrap();
+
+        let store = Store::open(Some(dir.clone()));
+        let mut names = store.list();
+        names.sort();
+        assert_eq!(names, vec!["email/work".to_string(), "myEmail".to_string()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn get_surfaces_command_failure() {
+        let store = Store::open(Some(env::temp_dir().join("pass-rust-test-nonexistent")));
+        match store.get("does-not-exist") {
+            Err(StoreError::Io(_)) | Err(StoreError::CommandFailed(_)) => {}
+            other => panic!("expected pass invocation to fail, got {:?}", other),
+        }
+    }
+}